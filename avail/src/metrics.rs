@@ -0,0 +1,205 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+// Exponential buckets, powers of two milliseconds from 1ms up to ~65s, plus one overflow
+// bucket for anything slower. 17 + 1 buckets total.
+const BUCKET_COUNT: usize = 18;
+
+fn bucket_for(millis: u64) -> usize {
+    for i in 0..BUCKET_COUNT - 1 {
+        if millis <= 1u64 << i {
+            return i;
+        }
+    }
+    BUCKET_COUNT - 1
+}
+
+fn bucket_upper_bound_ms(bucket: usize) -> u64 {
+    if bucket == BUCKET_COUNT - 1 {
+        u64::MAX
+    } else {
+        1u64 << bucket
+    }
+}
+
+/// A fixed-bucket latency histogram. Cheap to record into from hot paths and cheap to snapshot,
+/// at the cost of only approximate percentiles (to the resolution of the bucket boundaries).
+#[derive(Default)]
+pub struct Histogram {
+    buckets: [AtomicU64; BUCKET_COUNT],
+}
+
+impl Histogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, duration: Duration) {
+        let bucket = bucket_for(duration.as_millis() as u64);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().map(|b| b.load(Ordering::Relaxed)).sum()
+    }
+
+    /// Returns the smallest bucket upper bound whose cumulative count covers the `p`-th
+    /// percentile (0.0..=1.0), or `None` if nothing has been recorded yet.
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        let total = self.count();
+        if total == 0 {
+            return None;
+        }
+
+        let target = (p * total as f64).ceil() as u64;
+        let mut cumulative = 0u64;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            cumulative += count.load(Ordering::Relaxed);
+            if cumulative >= target.max(1) {
+                return Some(Duration::from_millis(bucket_upper_bound_ms(bucket)));
+            }
+        }
+
+        Some(Duration::from_millis(bucket_upper_bound_ms(BUCKET_COUNT - 1)))
+    }
+}
+
+/// Latency + outcome counters for a single instrumented operation.
+#[derive(Default)]
+pub struct OperationMetrics {
+    pub latency: Histogram,
+    successes: AtomicU64,
+    failures: AtomicU64,
+}
+
+impl OperationMetrics {
+    fn record(&self, duration: Duration, success: bool) {
+        self.latency.record(duration);
+        if success {
+            self.successes.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn successes(&self) -> u64 {
+        self.successes.load(Ordering::Relaxed)
+    }
+
+    pub fn failures(&self) -> u64 {
+        self.failures.load(Ordering::Relaxed)
+    }
+}
+
+/// Which `DaProvider` operation a latency measurement belongs to.
+pub enum Operation {
+    SendTransaction,
+    WaitForConfidence,
+    WaitForAppdata,
+    GetFinalizedAt,
+}
+
+struct MetricsInner {
+    send_transaction: OperationMetrics,
+    wait_for_confidence: OperationMetrics,
+    wait_for_appdata: OperationMetrics,
+    get_finalized_at: OperationMetrics,
+}
+
+/// A cheaply clonable handle to the DA operation metrics for a `DaProvider`. Replaces the
+/// ad-hoc `println!` timing with latency histograms and success/failure counters an operator
+/// or benchrunner can scrape.
+#[derive(Clone)]
+pub struct Metrics(Arc<MetricsInner>);
+
+impl Metrics {
+    pub fn new() -> Self {
+        Metrics(Arc::new(MetricsInner {
+            send_transaction: OperationMetrics::default(),
+            wait_for_confidence: OperationMetrics::default(),
+            wait_for_appdata: OperationMetrics::default(),
+            get_finalized_at: OperationMetrics::default(),
+        }))
+    }
+
+    pub fn record(&self, operation: Operation, duration: Duration, success: bool) {
+        let metrics = match operation {
+            Operation::SendTransaction => &self.0.send_transaction,
+            Operation::WaitForConfidence => &self.0.wait_for_confidence,
+            Operation::WaitForAppdata => &self.0.wait_for_appdata,
+            Operation::GetFinalizedAt => &self.0.get_finalized_at,
+        };
+        metrics.record(duration, success);
+    }
+
+    pub fn send_transaction(&self) -> &OperationMetrics {
+        &self.0.send_transaction
+    }
+
+    pub fn wait_for_confidence(&self) -> &OperationMetrics {
+        &self.0.wait_for_confidence
+    }
+
+    pub fn wait_for_appdata(&self) -> &OperationMetrics {
+        &self.0.wait_for_appdata
+    }
+
+    pub fn get_finalized_at(&self) -> &OperationMetrics {
+        &self.0.get_finalized_at
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_for_picks_smallest_covering_power_of_two() {
+        assert_eq!(bucket_for(0), 0);
+        assert_eq!(bucket_for(1), 0);
+        assert_eq!(bucket_for(2), 1);
+        assert_eq!(bucket_for(3), 2);
+        assert_eq!(bucket_for(4), 2);
+        assert_eq!(bucket_for(65536), BUCKET_COUNT - 2);
+        assert_eq!(bucket_for(100_000), BUCKET_COUNT - 1);
+    }
+
+    #[test]
+    fn histogram_counts_every_recording() {
+        let histogram = Histogram::new();
+        assert_eq!(histogram.count(), 0);
+
+        histogram.record(Duration::from_millis(1));
+        histogram.record(Duration::from_millis(500));
+        histogram.record(Duration::from_secs(120));
+
+        assert_eq!(histogram.count(), 3);
+    }
+
+    #[test]
+    fn percentile_is_none_when_empty() {
+        let histogram = Histogram::new();
+        assert_eq!(histogram.percentile(0.5), None);
+    }
+
+    #[test]
+    fn percentile_reflects_recorded_latencies() {
+        let histogram = Histogram::new();
+        for _ in 0..9 {
+            histogram.record(Duration::from_millis(1));
+        }
+        histogram.record(Duration::from_secs(60));
+
+        // p50 should land in the cheap, common-case bucket...
+        assert_eq!(histogram.percentile(0.5), Some(Duration::from_millis(1)));
+        // ...while p99 should be pulled up into the one slow outlier's bucket.
+        assert_eq!(histogram.percentile(0.99), Some(Duration::from_millis(65536)));
+    }
+}
@@ -1,12 +1,16 @@
 use core::time::Duration;
 
 use anyhow::anyhow;
+use async_stream::try_stream;
 use avail_subxt::api;
 use avail_subxt::api::runtime_types::sp_core::bounded::bounded_vec::BoundedVec;
-use avail_subxt::primitives::AvailExtrinsicParams;
+use avail_subxt::primitives::{AvailExtrinsicParams, HeaderExtension, KateCommitment};
 use avail_subxt::AvailConfig;
+use futures::Stream;
+use kate_recovery::proof::verify as verify_kate_proof;
 use reqwest::StatusCode;
 use sp_core::crypto::Pair as PairTrait;
+use sp_core::H256;
 use sp_keyring::sr25519::sr25519::Pair;
 use subxt::tx::PairSigner;
 use subxt::OnlineClient;
@@ -16,6 +20,23 @@ use crate::avail::AvailBlobTransaction;
 use crate::avail::AvailBlock;
 use crate::avail::AvailHeader;
 use crate::avail::{Confidence, ExtrinsicsData};
+use crate::metrics::{Metrics, Operation};
+
+fn default_confidence_threshold() -> f64 {
+    92.5
+}
+
+fn default_polling_timeout_secs() -> u64 {
+    60
+}
+
+fn default_polling_interval_secs() -> u64 {
+    2
+}
+
+fn default_sample_size() -> usize {
+    8
+}
 
 /// Runtime configuration for the DA service
 #[derive(Clone, PartialEq, serde::Deserialize, serde::Serialize)]
@@ -25,6 +46,15 @@ pub struct DaServiceConfig {
     //TODO: Safer strategy to load seed so it is not accidentally revealed.
     pub seed: String,
     pub app_id: u32,
+    #[serde(default = "default_confidence_threshold")]
+    pub confidence_threshold: f64,
+    #[serde(default = "default_polling_timeout_secs")]
+    pub polling_timeout_secs: u64,
+    #[serde(default = "default_polling_interval_secs")]
+    pub polling_interval_secs: u64,
+    /// Number of cells sampled per block for independent data-availability verification.
+    #[serde(default = "default_sample_size")]
+    pub sample_size: usize,
 }
 
 #[derive(Clone)]
@@ -33,12 +63,18 @@ pub struct DaProvider {
     pub light_client_url: String,
     app_id: u32,
     signer: PairSigner<AvailConfig, Pair>,
+    confidence_threshold: f64,
+    polling_timeout: Duration,
+    polling_interval: Duration,
+    sample_size: usize,
+    metrics: Metrics,
 }
 
 impl DaProvider {
     fn appdata_url(&self, block_num: u64) -> String {
         let light_client_url = self.light_client_url.clone();
-        format!("{light_client_url}/v1/appdata/{block_num}")
+        let app_id = self.app_id;
+        format!("{light_client_url}/v1/appdata/{block_num}?app_id={app_id}")
     }
 
     fn confidence_url(&self, block_num: u64) -> String {
@@ -46,6 +82,11 @@ impl DaProvider {
         format!("{light_client_url}/v1/confidence/{block_num}")
     }
 
+    fn cell_url(&self, block_num: u64, row: u32, col: u16) -> String {
+        let light_client_url = self.light_client_url.clone();
+        format!("{light_client_url}/v1/cell/{block_num}/{row}/{col}")
+    }
+
     pub async fn new(config: DaServiceConfig) -> Self {
         let pair = Pair::from_string_with_seed(&config.seed, None).unwrap();
         let signer = PairSigner::<AvailConfig, Pair>::new(pair.0.clone());
@@ -59,33 +100,63 @@ impl DaProvider {
             node_client,
             light_client_url,
             signer,
-            app_id: config.app_id
+            app_id: config.app_id,
+            confidence_threshold: config.confidence_threshold,
+            polling_timeout: Duration::from_secs(config.polling_timeout_secs),
+            polling_interval: Duration::from_secs(config.polling_interval_secs),
+            sample_size: config.sample_size,
+            metrics: Metrics::new(),
         }
     }
+
+    /// A cheaply clonable handle to this provider's latency histograms and success/failure
+    /// counters for `send_transaction`, `wait_for_confidence`, `wait_for_appdata`, and
+    /// `get_finalized_at`, for operators or a benchrunner to scrape.
+    pub fn metrics_snapshot(&self) -> Metrics {
+        self.metrics.clone()
+    }
 }
 
-const POLLING_TIMEOUT: Duration = Duration::from_secs(60);
-const POLLING_INTERVAL: Duration = Duration::from_secs(2);
+// Mortality window (in blocks) given to each batched submission's extrinsic era.
+const MORTALITY_PERIOD: u64 = 32;
+
+/// How far to wait for a submitted extrinsic before `send_transactions` considers it settled.
+pub enum FinalityLevel {
+    InBlock,
+    Finalized,
+}
 
-async fn wait_for_confidence(confidence_url: &str) -> anyhow::Result<()> {
+/// Persists how far `DaProvider::sync_from` has progressed so a restart resumes from the last
+/// successfully processed height instead of replaying the whole chain.
+pub trait SyncCursor {
+    fn load(&self) -> Result<Option<u64>, anyhow::Error>;
+    fn store(&self, height: u64) -> Result<(), anyhow::Error>;
+}
+
+async fn wait_for_confidence(
+    confidence_url: &str,
+    confidence_threshold: f64,
+    polling_timeout: Duration,
+    polling_interval: Duration,
+) -> anyhow::Result<()> {
     let start_time = std::time::Instant::now();
 
     loop {
-        if start_time.elapsed() >= POLLING_TIMEOUT {
+        if start_time.elapsed() >= polling_timeout {
             return Err(anyhow!("Timeout..."));
         }
 
         let response = reqwest::get(confidence_url).await?;
         if response.status() != StatusCode::OK {
             println!("Confidence not received");
-            tokio::time::sleep(POLLING_INTERVAL).await;
+            tokio::time::sleep(polling_interval).await;
             continue;
         }
 
         let response: Confidence = serde_json::from_str(&response.text().await?)?;
-        if response.confidence < 92.5 {
+        if response.confidence < confidence_threshold {
             println!("Confidence not reached");
-            tokio::time::sleep(POLLING_INTERVAL).await;
+            tokio::time::sleep(polling_interval).await;
             continue;
         }
 
@@ -95,11 +166,16 @@ async fn wait_for_confidence(confidence_url: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn wait_for_appdata(appdata_url: &str, block: u32) -> anyhow::Result<ExtrinsicsData> {
+async fn wait_for_appdata(
+    appdata_url: &str,
+    block: u32,
+    polling_timeout: Duration,
+    polling_interval: Duration,
+) -> anyhow::Result<ExtrinsicsData> {
     let start_time = std::time::Instant::now();
 
     loop {
-        if start_time.elapsed() >= POLLING_TIMEOUT {
+        if start_time.elapsed() >= polling_timeout {
             return Err(anyhow!("Timeout..."));
         }
 
@@ -111,7 +187,7 @@ async fn wait_for_appdata(appdata_url: &str, block: u32) -> anyhow::Result<Extri
             });
         }
         if response.status() != StatusCode::OK {
-            tokio::time::sleep(POLLING_INTERVAL).await;
+            tokio::time::sleep(polling_interval).await;
             continue;
         }
 
@@ -120,16 +196,114 @@ async fn wait_for_appdata(appdata_url: &str, block: u32) -> anyhow::Result<Extri
     }
 }
 
+#[derive(serde::Deserialize)]
+struct CellProof {
+    // hex-encoded 32-byte scalar value committed at this cell
+    value: String,
+    // hex-encoded 48-byte KZG opening proof for the cell
+    proof: String,
+}
+
+// Derives up to `count` *distinct* pseudo-random (row, col) cell positions from the block hash,
+// so DAS sampling is deterministic and reproducible across verifiers instead of relying on local
+// randomness. Collisions are skipped by trying the next hash-derived index rather than accepted,
+// since an accidental duplicate would silently verify fewer than `count` distinct cells. The
+// target is capped at the grid size so this always terminates, even for a tiny block.
+fn sample_cell_positions(hash: H256, rows: u16, cols: u16, count: usize) -> Vec<(u16, u16)> {
+    let grid_size = rows as usize * cols as usize;
+    let target = count.min(grid_size.max(1));
+
+    let mut seen = std::collections::HashSet::with_capacity(target);
+    let mut positions = Vec::with_capacity(target);
+    let mut i: u32 = 0;
+    while positions.len() < target {
+        let seed = sp_core::blake2_256(&[hash.as_bytes(), &i.to_le_bytes()].concat());
+        let index = u32::from_le_bytes(seed[0..4].try_into().unwrap());
+        let row = (index % rows as u32) as u16;
+        let col = ((index / rows as u32) % cols as u32) as u16;
+        if seen.insert((row, col)) {
+            positions.push((row, col));
+        }
+        i += 1;
+    }
+    positions
+}
+
+// Extracts the KZG/data-root commitment out of whichever header-extension version is present.
+fn kate_commitment(header: &avail_subxt::primitives::Header) -> Result<KateCommitment, anyhow::Error> {
+    match &header.extension {
+        HeaderExtension::V1(ext) => Ok(ext.commitment.clone()),
+        HeaderExtension::V2(ext) => Ok(ext.commitment.clone()),
+        HeaderExtension::V3(ext) => Ok(ext.commitment.clone()),
+    }
+}
+
 impl DaProvider {
     // Make an RPC call to the node to get the finalized block at the given height, if one exists.
     // If no such block exists, block until one does.
     pub async fn get_finalized_at(&self, height: u64) -> Result<AvailBlock, anyhow::Error> {
+        self.get_finalized_at_inner(height, false).await
+    }
+
+    // Same as `get_finalized_at`, but when `skip_confidence` is set the light-client confidence
+    // poll is skipped entirely. This is safe for heights at or behind the chain's finalized
+    // watermark, where confidence is a foregone conclusion, and saves a round trip per block.
+    async fn get_finalized_at_inner(
+        &self,
+        height: u64,
+        skip_confidence: bool,
+    ) -> Result<AvailBlock, anyhow::Error> {
+        let started_at = std::time::Instant::now();
+        let result = self
+            .get_finalized_at_uninstrumented(height, skip_confidence)
+            .await;
+        self.metrics.record(
+            Operation::GetFinalizedAt,
+            started_at.elapsed(),
+            result.is_ok(),
+        );
+        result
+    }
+
+    async fn get_finalized_at_uninstrumented(
+        &self,
+        height: u64,
+        skip_confidence: bool,
+    ) -> Result<AvailBlock, anyhow::Error> {
         let node_client = self.node_client.clone();
-        let confidence_url = self.confidence_url(height);
         let appdata_url = self.appdata_url(height);
 
-        wait_for_confidence(&confidence_url).await?;
-        let appdata = wait_for_appdata(&appdata_url, height as u32).await?;
+        if !skip_confidence {
+            let confidence_url = self.confidence_url(height);
+            let started_at = std::time::Instant::now();
+            let result = wait_for_confidence(
+                &confidence_url,
+                self.confidence_threshold,
+                self.polling_timeout,
+                self.polling_interval,
+            )
+            .await;
+            self.metrics.record(
+                Operation::WaitForConfidence,
+                started_at.elapsed(),
+                result.is_ok(),
+            );
+            result?;
+        }
+        let started_at = std::time::Instant::now();
+        let appdata = wait_for_appdata(
+            &appdata_url,
+            height as u32,
+            self.polling_timeout,
+            self.polling_interval,
+        )
+        .await;
+        self.metrics.record(
+            Operation::WaitForAppdata,
+            started_at.elapsed(),
+            appdata.is_ok(),
+        );
+        let appdata = appdata?;
         println!("Appdata: {:?}", appdata);
 
         let hash = node_client
@@ -138,9 +312,11 @@ impl DaProvider {
             .await?
             .unwrap();
 
-        let header = node_client.rpc().header(Some(hash)).await?.unwrap();
+        let raw_header = node_client.rpc().header(Some(hash)).await?.unwrap();
+        self.verify_data_availability_sampling(height, hash, &raw_header)
+            .await?;
 
-        let header = AvailHeader::new(header, hash);
+        let header = AvailHeader::new(raw_header, hash);
         let transactions = appdata
             .extrinsics
             .iter()
@@ -152,13 +328,117 @@ impl DaProvider {
         })
     }
 
+    // Independently verifies data availability for `height` instead of trusting the light
+    // client's self-reported confidence percentage: samples `sample_size` cells at indices
+    // derived deterministically from the block hash, fetches each cell's KZG proof from the
+    // light client, and checks it against the commitments in the header's data-root extension.
+    // A single failed proof fails the whole block.
+    async fn verify_data_availability_sampling(
+        &self,
+        height: u64,
+        hash: H256,
+        header: &avail_subxt::primitives::Header,
+    ) -> Result<(), anyhow::Error> {
+        let commitment = kate_commitment(header)?;
+        if commitment.rows == 0 || commitment.cols == 0 {
+            return Ok(());
+        }
+
+        for (row, col) in sample_cell_positions(hash, commitment.rows, commitment.cols, self.sample_size) {
+            let cell_url = self.cell_url(height, row as u32, col);
+            let response = reqwest::get(&cell_url).await?;
+            if response.status() != StatusCode::OK {
+                return Err(anyhow!(
+                    "missing DAS cell (row={row}, col={col}) for block {height}"
+                ));
+            }
+            let cell: CellProof = serde_json::from_str(&response.text().await?)?;
+            let value_bytes = hex::decode(cell.value.trim_start_matches("0x"))?;
+            let proof_bytes = hex::decode(cell.proof.trim_start_matches("0x"))?;
+
+            // A KZG opening proof verifies a claimed *value* against the commitment at a given
+            // position; the proof bytes alone aren't enough; `value_bytes` must be passed too.
+            let verified = verify_kate_proof(
+                &commitment.commitment,
+                commitment.cols,
+                row,
+                col,
+                &value_bytes,
+                &proof_bytes,
+            )
+            .map_err(|e| anyhow!("DAS proof verification errored: {e:?}"))?;
+            if !verified {
+                return Err(anyhow!(
+                    "DAS proof failed for block {height} at cell (row={row}, col={col})"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     // Make an RPC call to the node to get the block at the given height
     // If no such block exists, block until one does.
     pub async fn get_block_at(&self, height: u64) -> Result<AvailBlock, anyhow::Error> {
         self.get_finalized_at(height).await
     }
 
+    // The height of the chain's current finalized head, used by `sync_from` as the watermark
+    // at or below which blocks are known-confirmed and don't need a confidence poll.
+    async fn finalized_head_number(&self) -> Result<u64, anyhow::Error> {
+        let hash = self.node_client.rpc().finalized_head().await?;
+        let header = self
+            .node_client
+            .rpc()
+            .header(Some(hash))
+            .await?
+            .ok_or_else(|| anyhow!("finalized head {hash:?} has no header"))?;
+        Ok(header.number.into())
+    }
+
+    // Walks finalized heights sequentially starting at `start_height` (or one past whatever
+    // `cursor` last stored, if anything), yielding an `AvailBlock` containing only this
+    // provider's app-id extrinsics for each height. The cursor is persisted after every
+    // successfully yielded block so a crashed replay resumes instead of re-downloading and
+    // re-verifying old blocks.
+    pub fn sync_from<'a, C: SyncCursor + 'a>(
+        &'a self,
+        start_height: u64,
+        cursor: &'a C,
+    ) -> impl Stream<Item = Result<AvailBlock, anyhow::Error>> + 'a {
+        try_stream! {
+            let mut height = match cursor.load()? {
+                Some(last_processed) => last_processed + 1,
+                None => start_height,
+            };
+            let watermark = self.finalized_head_number().await?;
+
+            loop {
+                let block = self
+                    .get_finalized_at_inner(height, height <= watermark)
+                    .await?;
+                yield block;
+                // Only persist the cursor once the consumer has resumed polling the stream
+                // (which, for the usual `while let Some(block) = stream.next().await { apply(block) }`
+                // consumer, only happens after it has finished applying the block we just
+                // yielded). Storing before the yield would mark a height "done" the instant
+                // we produce it, so a crash between yield and the consumer finishing its
+                // replay would silently skip that block on resume.
+                cursor.store(height)?;
+                height += 1;
+            }
+        }
+    }
+
     pub async fn send_transaction(&self, blob: &[u8]) -> Result<(), anyhow::Error> {
+        let started_at = std::time::Instant::now();
+        let result = self.send_transaction_uninstrumented(blob).await;
+        self.metrics
+            .record(Operation::SendTransaction, started_at.elapsed(), result.is_ok());
+        result
+    }
+
+    async fn send_transaction_uninstrumented(&self, blob: &[u8]) -> Result<(), anyhow::Error> {
     println!("DStarted submissions");
 
       let data_transfer = api::tx()
@@ -167,7 +447,7 @@ impl DaProvider {
     println!("created extrinsic");
 
       println!("blob {:?}", &blob);
-        
+
       let extrinsic_params = AvailExtrinsicParams::new_with_app_id(self.app_id.into());
       println!("Signing and sending extrinsic to app id signer: {:#?}", &self.signer.account_id());
 
@@ -180,4 +460,217 @@ impl DaProvider {
 
       Ok(())
     }
+
+    // Signs and submits a `create_application_key` extrinsic for `key`, waits for it to be
+    // finalized, and returns the `app_id` allocated by the chain. Lets a fresh deployment
+    // bootstrap its own app namespace instead of requiring a hand-configured `app_id`.
+    //
+    // Note: this does not update `self.app_id` to the newly created id. `send_transaction(s)`
+    // on this provider will keep using the `app_id` it was constructed with, so a caller that
+    // wants to submit blobs under the new key needs to rebuild its `DaProvider` (or `DaServiceConfig`)
+    // with the returned id.
+    pub async fn create_application_key(&self, key: &[u8]) -> Result<u32, anyhow::Error> {
+        let create_key = api::tx()
+            .data_availability()
+            .create_application_key(BoundedVec(key.to_vec()));
+
+        // The `CheckAppId` signed extension only allows a non-zero `AppId` on
+        // `DataAvailability::submit_data`; every other call, including this one, must be
+        // submitted with `AppId(0)` or the node rejects it as invalid.
+        let extrinsic_params = AvailExtrinsicParams::new_with_app_id(0);
+
+        let events = self
+            .node_client
+            .tx()
+            .sign_and_submit_then_watch(&create_key, &self.signer, extrinsic_params)
+            .await?
+            .wait_for_finalized_success()
+            .await?;
+
+        let created = events
+            .find_first::<api::data_availability::events::ApplicationKeyCreated>()?
+            .ok_or_else(|| anyhow!("ApplicationKeyCreated event not found"))?;
+
+        println!("Application key created: {:#?}", created.id.0);
+
+        Ok(created.id.0)
+    }
+
+    // Reads the `app_id` currently registered for `key`, if any, so callers can look up an
+    // existing id idempotently and fall back to `create_application_key` when it is missing.
+    pub async fn query_application_key(&self, key: &[u8]) -> Result<Option<u32>, anyhow::Error> {
+        let query = api::storage()
+            .data_availability()
+            .app_keys(BoundedVec(key.to_vec()));
+
+        let app_key = self
+            .node_client
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&query)
+            .await?;
+
+        Ok(app_key.map(|app_key| app_key.app_id.0))
+    }
+
+    // Submits `blobs` as one `submit_data` extrinsic each, fetching the signer's nonce once and
+    // assigning the rest locally so every extrinsic in a round is signed and handed to the pool
+    // back-to-back, without waiting for one to be included before submitting the next — then
+    // watches all of that round's extrinsics to `finality` concurrently. A genuine single
+    // `utility.batch_all` isn't used here: batching would merge every blob into one extrinsic
+    // with one hash, but callers need a distinct `H256` per blob to track each `StateUpdate`
+    // individually, and a failure partway through a batch would be all-or-nothing for the whole
+    // round instead of retryable per blob. Submitting many small extrinsics concurrently gets
+    // the same "don't pay a full round trip per blob" win without losing that granularity.
+    //
+    // A recoverable RPC error or a transaction dropped from the pool requeues that blob for the
+    // next round with a freshly queried nonce, up to `max_retries` rounds.
+    pub async fn send_transactions(
+        &self,
+        blobs: &[&[u8]],
+        finality: FinalityLevel,
+        max_retries: u32,
+    ) -> Result<Vec<H256>, anyhow::Error> {
+        if blobs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut nonce = self.account_nonce().await?;
+        let mut results: Vec<Option<H256>> = vec![None; blobs.len()];
+        let mut pending: Vec<usize> = (0..blobs.len()).collect();
+        let mut round = 0;
+
+        while !pending.is_empty() {
+            // Fetched once per round, not per blob: re-fetching inside the loop below would
+            // reintroduce a network round trip before every submission.
+            let best_block = self
+                .node_client
+                .rpc()
+                .header(None)
+                .await?
+                .ok_or_else(|| anyhow!("chain has no best block header"))?;
+
+            // Submit this round's blobs sequentially (each needs the next nonce in order), but
+            // don't await inclusion/finality in between — that's the part that used to cost a
+            // full round trip per blob.
+            let mut submissions = Vec::with_capacity(pending.len());
+            for &idx in &pending {
+                let call = api::tx()
+                    .data_availability()
+                    .submit_data(BoundedVec(blobs[idx].to_vec()));
+                let params = AvailExtrinsicParams::new_with_app_id(self.app_id.into())
+                    .nonce(nonce)
+                    .mortal(&best_block, MORTALITY_PERIOD);
+
+                let submission = self
+                    .node_client
+                    .tx()
+                    .sign_and_submit_then_watch(&call, &self.signer, params)
+                    .await
+                    .map_err(anyhow::Error::from);
+
+                // Only advance the nonce when the chain actually accepted this submission at
+                // this nonce. Advancing unconditionally would hand the next blob in this round
+                // a nonce one higher than what's actually expected after a failed submission —
+                // Substrate queues that as pending-but-unincludable rather than rejecting it,
+                // so the later `wait_for_in_block`/`wait_for_finalized` calls would hang forever
+                // instead of ever producing an error for the retry loop to act on.
+                if submission.is_ok() {
+                    nonce += 1;
+                } else if let Err(e) = &submission {
+                    println!("blob {idx} submission failed, will retry: {e:?}");
+                }
+                submissions.push((idx, submission));
+            }
+
+            // Now watch every submission in this round concurrently instead of one at a time.
+            let watched = futures::future::join_all(submissions.into_iter().map(
+                |(idx, submission)| async move {
+                    let hash = match submission {
+                        Ok(progress) => match finality {
+                            FinalityLevel::InBlock => progress
+                                .wait_for_in_block()
+                                .await
+                                .map(|tx| tx.extrinsic_hash())
+                                .map_err(anyhow::Error::from),
+                            FinalityLevel::Finalized => progress
+                                .wait_for_finalized()
+                                .await
+                                .map(|tx| tx.extrinsic_hash())
+                                .map_err(anyhow::Error::from),
+                        },
+                        Err(e) => Err(e),
+                    };
+                    (idx, hash)
+                },
+            ))
+            .await;
+
+            let mut retry = Vec::new();
+            for (idx, hash) in watched {
+                match hash {
+                    Ok(hash) => results[idx] = Some(hash),
+                    Err(e) => {
+                        println!("blob {idx} failed this round, will retry: {e:?}");
+                        retry.push(idx);
+                    }
+                }
+            }
+
+            pending = retry;
+            if !pending.is_empty() {
+                if round >= max_retries {
+                    return Err(anyhow!(
+                        "{} blob(s) still failing after {max_retries} retries",
+                        pending.len()
+                    ));
+                }
+                round += 1;
+                nonce = self.account_nonce().await?;
+            }
+        }
+
+        Ok(results
+            .into_iter()
+            .map(|hash| hash.expect("every index is resolved before returning"))
+            .collect())
+    }
+
+    async fn account_nonce(&self) -> Result<u32, anyhow::Error> {
+        Ok(self
+            .node_client
+            .rpc()
+            .system_account_next_index(self.signer.account_id())
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sample_cell_positions;
+    use sp_core::H256;
+
+    #[test]
+    fn sample_cell_positions_is_deterministic() {
+        let hash = H256::repeat_byte(7);
+        let first = sample_cell_positions(hash, 16, 16, 8);
+        let second = sample_cell_positions(hash, 16, 16, 8);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn sample_cell_positions_has_no_duplicates() {
+        let hash = H256::repeat_byte(3);
+        let positions = sample_cell_positions(hash, 16, 16, 8);
+        let unique: std::collections::HashSet<_> = positions.iter().collect();
+        assert_eq!(positions.len(), unique.len());
+    }
+
+    #[test]
+    fn sample_cell_positions_caps_at_grid_size() {
+        let hash = H256::repeat_byte(1);
+        let positions = sample_cell_positions(hash, 2, 2, 8);
+        assert_eq!(positions.len(), 4);
+    }
 }
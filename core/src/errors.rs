@@ -0,0 +1,20 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidTransition(String),
+    NotFound(String),
+    Crypto(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidTransition(msg) => write!(f, "invalid state transition: {msg}"),
+            Error::NotFound(msg) => write!(f, "not found: {msg}"),
+            Error::Crypto(msg) => write!(f, "cryptographic error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -2,6 +2,7 @@ pub use primitive_types::U256;
 
 
 pub mod errors;
+pub mod memo;
 pub mod nft;
 pub mod payments;
 pub mod state;
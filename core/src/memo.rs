@@ -0,0 +1,130 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use x25519_dalek::{EphemeralSecret, PublicKey, SharedSecret, StaticSecret};
+
+use crate::errors::Error;
+
+/// An encrypted off-state message attached to an NFT transfer (see
+/// [`crate::nft::Nft::transfer_with_memo`]). The sender encrypts a note to the recipient's
+/// X25519 public key; anyone else observing the DA blob sees only opaque ciphertext.
+pub struct Memo {
+    ephemeral_pk: [u8; 32],
+    nonce: [u8; 12],
+    ciphertext: Vec<u8>,
+}
+
+// Raw X25519 Diffie-Hellman output isn't uniformly random and carries no domain separation, so
+// it must be run through a KDF before use as a symmetric key (x25519-dalek's own docs call this
+// out). HKDF-SHA256 over the shared secret, bound to both public keys, gives us that.
+fn derive_key(shared_secret: &SharedSecret, ephemeral_pk: &PublicKey, recipient_pk: &PublicKey) -> [u8; 32] {
+    let hkdf = Hkdf::<Sha256>::new(None, shared_secret.as_bytes());
+    let mut info = Vec::with_capacity(b"zknft-memo-v1".len() + 64);
+    info.extend_from_slice(b"zknft-memo-v1");
+    info.extend_from_slice(ephemeral_pk.as_bytes());
+    info.extend_from_slice(recipient_pk.as_bytes());
+
+    let mut key = [0u8; 32];
+    hkdf.expand(&info, &mut key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    key
+}
+
+impl Memo {
+    pub fn encrypt(recipient_pk: &PublicKey, plaintext: &[u8]) -> Result<Self, Error> {
+        let ephemeral_secret = EphemeralSecret::random_from_rng(OsRng);
+        let ephemeral_pk = PublicKey::from(&ephemeral_secret);
+        let shared_secret = ephemeral_secret.diffie_hellman(recipient_pk);
+        let key = derive_key(&shared_secret, &ephemeral_pk, recipient_pk);
+
+        let cipher = Aes256Gcm::new_from_slice(&key)
+            .map_err(|e| Error::Crypto(format!("failed to init memo cipher: {e}")))?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext)
+            .map_err(|e| Error::Crypto(format!("failed to encrypt memo: {e}")))?;
+
+        Ok(Memo {
+            ephemeral_pk: ephemeral_pk.to_bytes(),
+            nonce: nonce.into(),
+            ciphertext,
+        })
+    }
+
+    pub fn decrypt(&self, recipient_sk: &StaticSecret) -> Result<Vec<u8>, Error> {
+        let ephemeral_pk = PublicKey::from(self.ephemeral_pk);
+        let recipient_pk = PublicKey::from(recipient_sk);
+        let shared_secret = recipient_sk.diffie_hellman(&ephemeral_pk);
+        let key = derive_key(&shared_secret, &ephemeral_pk, &recipient_pk);
+
+        let cipher = Aes256Gcm::new_from_slice(&key)
+            .map_err(|e| Error::Crypto(format!("failed to init memo cipher: {e}")))?;
+        cipher
+            .decrypt(Nonce::from_slice(&self.nonce), self.ciphertext.as_ref())
+            .map_err(|e| Error::Crypto(format!("failed to decrypt memo: {e}")))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(32 + 12 + self.ciphertext.len());
+        bytes.extend_from_slice(&self.ephemeral_pk);
+        bytes.extend_from_slice(&self.nonce);
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 32 + 12 {
+            return Err(Error::Crypto("memo bytes too short".to_string()));
+        }
+        let mut ephemeral_pk = [0u8; 32];
+        ephemeral_pk.copy_from_slice(&bytes[0..32]);
+        let mut nonce = [0u8; 12];
+        nonce.copy_from_slice(&bytes[32..44]);
+        let ciphertext = bytes[44..].to_vec();
+
+        Ok(Memo {
+            ephemeral_pk,
+            nonce,
+            ciphertext,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng as RandOsRng;
+
+    #[test]
+    fn encrypt_decrypt_roundtrip() {
+        let recipient_secret = StaticSecret::random_from_rng(RandOsRng);
+        let recipient_pk = PublicKey::from(&recipient_secret);
+
+        let memo = Memo::encrypt(&recipient_pk, b"hello recipient").unwrap();
+        let plaintext = memo.decrypt(&recipient_secret).unwrap();
+
+        assert_eq!(plaintext, b"hello recipient");
+    }
+
+    #[test]
+    fn wrong_recipient_cannot_decrypt() {
+        let recipient_secret = StaticSecret::random_from_rng(RandOsRng);
+        let recipient_pk = PublicKey::from(&recipient_secret);
+        let other_secret = StaticSecret::random_from_rng(RandOsRng);
+
+        let memo = Memo::encrypt(&recipient_pk, b"secret note").unwrap();
+        assert!(memo.decrypt(&other_secret).is_err());
+    }
+
+    #[test]
+    fn to_bytes_from_bytes_roundtrip() {
+        let recipient_secret = StaticSecret::random_from_rng(RandOsRng);
+        let recipient_pk = PublicKey::from(&recipient_secret);
+
+        let memo = Memo::encrypt(&recipient_pk, b"roundtrip").unwrap();
+        let restored = Memo::from_bytes(&memo.to_bytes()).unwrap();
+
+        assert_eq!(restored.decrypt(&recipient_secret).unwrap(), b"roundtrip");
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+
+use crate::errors::Error;
+use crate::memo::Memo;
+use crate::traits::{StateMachine, ZkVMStateMachine};
+use crate::types::StateUpdate;
+use crate::U256;
+
+/// Calls accepted by the `Nft` state machine.
+pub enum NftCall {
+    Mint {
+        token_id: U256,
+        owner: Vec<u8>,
+    },
+    Transfer {
+        token_id: U256,
+        from: Vec<u8>,
+        to: Vec<u8>,
+        memo: Option<Memo>,
+    },
+}
+
+/// In-memory NFT ledger: maps a token id to the public key bytes of its current owner.
+pub struct Nft {
+    owners: HashMap<U256, Vec<u8>>,
+}
+
+impl StateMachine<U256> for Nft {
+    type CallParams = NftCall;
+
+    fn new() -> Self {
+        Nft {
+            owners: HashMap::new(),
+        }
+    }
+
+    fn load() -> Self {
+        <Self as StateMachine<U256>>::new()
+    }
+
+    fn call(&mut self, call: Self::CallParams) -> Result<StateUpdate<U256>, Error> {
+        match call {
+            NftCall::Mint { token_id, owner } => self.mint(token_id, owner),
+            NftCall::Transfer {
+                token_id,
+                from,
+                to,
+                memo,
+            } => self.transfer_with_memo(token_id, from, to, memo),
+        }
+    }
+}
+
+/// Verifies a claimed `Nft` transition without needing to hold the live ledger: it only checks
+/// that the call and the `StateUpdate` agree on which token moved, and that the transfer isn't a
+/// no-op. `state_update.memo` is intentionally never inspected — an attached memo is
+/// committed-but-opaque data and has no bearing on whether the transition is valid.
+impl ZkVMStateMachine<U256> for Nft {
+    type CallParams = NftCall;
+
+    fn new() -> Self {
+        <Self as StateMachine<U256>>::new()
+    }
+
+    fn call(&self, call: Self::CallParams, state_update: StateUpdate<U256>) -> Result<(), Error> {
+        let token_id = match &call {
+            NftCall::Mint { token_id, .. } => *token_id,
+            NftCall::Transfer { token_id, from, to, .. } => {
+                if from == to {
+                    return Err(Error::InvalidTransition(format!(
+                        "token {token_id} transferred to its current owner"
+                    )));
+                }
+                *token_id
+            }
+        };
+
+        if state_update.value != token_id {
+            return Err(Error::InvalidTransition(format!(
+                "state update for token {} does not match call for token {token_id}",
+                state_update.value
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Nft {
+    /// Mints `token_id` directly to `owner`. Minting is its own call rather than a `transfer`
+    /// variant because the first-ever owner of a token has no prior owner to transition from.
+    pub fn mint(&mut self, token_id: U256, owner: Vec<u8>) -> Result<StateUpdate<U256>, Error> {
+        if self.owners.contains_key(&token_id) {
+            return Err(Error::InvalidTransition(format!(
+                "token {token_id} already minted"
+            )));
+        }
+
+        self.owners.insert(token_id, owner);
+
+        Ok(StateUpdate {
+            value: token_id,
+            memo: None,
+        })
+    }
+
+    /// Transfers `token_id` from `from` to `to` with no memo attached.
+    pub fn transfer(
+        &mut self,
+        token_id: U256,
+        from: Vec<u8>,
+        to: Vec<u8>,
+    ) -> Result<StateUpdate<U256>, Error> {
+        self.transfer_with_memo(token_id, from, to, None)
+    }
+
+    /// Transfers `token_id` from `from` to `to`, optionally carrying an encrypted memo for the
+    /// recipient. The memo is embedded in the resulting `StateUpdate` as committed-but-opaque
+    /// bytes: it never affects whether the transfer is valid, so `ZkVMStateMachine::call`
+    /// verifies it exactly as it would a transfer with no memo.
+    pub fn transfer_with_memo(
+        &mut self,
+        token_id: U256,
+        from: Vec<u8>,
+        to: Vec<u8>,
+        memo: Option<Memo>,
+    ) -> Result<StateUpdate<U256>, Error> {
+        let current_owner = self
+            .owners
+            .get(&token_id)
+            .ok_or_else(|| Error::NotFound(format!("token {token_id}")))?;
+
+        if current_owner != &from {
+            return Err(Error::InvalidTransition(format!(
+                "token {token_id} is not owned by the sender"
+            )));
+        }
+
+        self.owners.insert(token_id, to);
+
+        Ok(StateUpdate {
+            value: token_id,
+            memo: memo.map(|memo| memo.to_bytes()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+    use x25519_dalek::{PublicKey, StaticSecret};
+
+    #[test]
+    fn mint_then_transfer_succeeds() {
+        let mut nft = <Nft as StateMachine<U256>>::new();
+        let token_id = U256::from(1);
+        nft.mint(token_id, b"alice".to_vec()).unwrap();
+
+        let update = nft
+            .transfer(token_id, b"alice".to_vec(), b"bob".to_vec())
+            .unwrap();
+
+        assert_eq!(update.value, token_id);
+        assert_eq!(update.memo, None);
+    }
+
+    #[test]
+    fn mint_twice_fails() {
+        let mut nft = <Nft as StateMachine<U256>>::new();
+        let token_id = U256::from(1);
+        nft.mint(token_id, b"alice".to_vec()).unwrap();
+
+        assert!(nft.mint(token_id, b"bob".to_vec()).is_err());
+    }
+
+    #[test]
+    fn transfer_from_non_owner_fails() {
+        let mut nft = <Nft as StateMachine<U256>>::new();
+        let token_id = U256::from(1);
+        nft.mint(token_id, b"alice".to_vec()).unwrap();
+
+        let result = nft.transfer(token_id, b"mallory".to_vec(), b"bob".to_vec());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn transfer_without_mint_fails() {
+        let mut nft = <Nft as StateMachine<U256>>::new();
+        let result = nft.transfer(U256::from(1), b"alice".to_vec(), b"bob".to_vec());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn transfer_with_memo_embeds_memo_bytes() {
+        let mut nft = <Nft as StateMachine<U256>>::new();
+        let token_id = U256::from(1);
+        nft.mint(token_id, b"alice".to_vec()).unwrap();
+
+        let recipient_secret = StaticSecret::random_from_rng(OsRng);
+        let recipient_pk = PublicKey::from(&recipient_secret);
+        let memo = Memo::encrypt(&recipient_pk, b"enjoy your nft").unwrap();
+        let memo_bytes = memo.to_bytes();
+
+        let update = nft
+            .transfer_with_memo(token_id, b"alice".to_vec(), b"bob".to_vec(), Some(memo))
+            .unwrap();
+
+        assert_eq!(update.memo, Some(memo_bytes));
+    }
+
+    #[test]
+    fn zkvm_state_machine_accepts_with_or_without_memo() {
+        let nft = <Nft as ZkVMStateMachine<U256>>::new();
+        let token_id = U256::from(1);
+
+        let call_a = NftCall::Transfer {
+            token_id,
+            from: b"alice".to_vec(),
+            to: b"bob".to_vec(),
+            memo: None,
+        };
+        let update_without_memo = StateUpdate {
+            value: token_id,
+            memo: None,
+        };
+        assert!(ZkVMStateMachine::call(&nft, call_a, update_without_memo).is_ok());
+
+        let call_b = NftCall::Transfer {
+            token_id,
+            from: b"alice".to_vec(),
+            to: b"bob".to_vec(),
+            memo: None,
+        };
+        let update_with_memo = StateUpdate {
+            value: token_id,
+            memo: Some(vec![1, 2, 3]),
+        };
+        assert!(ZkVMStateMachine::call(&nft, call_b, update_with_memo).is_ok());
+    }
+
+    #[test]
+    fn zkvm_state_machine_rejects_mismatched_token() {
+        let nft = <Nft as ZkVMStateMachine<U256>>::new();
+        let call = NftCall::Transfer {
+            token_id: U256::from(1),
+            from: b"alice".to_vec(),
+            to: b"bob".to_vec(),
+            memo: None,
+        };
+        let update = StateUpdate {
+            value: U256::from(2),
+            memo: None,
+        };
+        assert!(ZkVMStateMachine::call(&nft, call, update).is_err());
+    }
+}
@@ -0,0 +1,10 @@
+/// A state transition produced by a `StateMachine` and replayed by a `ZkVMStateMachine`.
+///
+/// `memo` carries opaque bytes (e.g. an encrypted off-state message) that travel alongside the
+/// update through the DA blob. It is committed but never inspected by the verifier, so attaching
+/// one has no bearing on whether the transition is valid.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct StateUpdate<V> {
+    pub value: V,
+    pub memo: Option<Vec<u8>>,
+}